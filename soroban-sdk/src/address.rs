@@ -38,31 +38,32 @@ impl Debug for Address {
         write!(f, "Address(..)")?;
         #[cfg(not(target_family = "wasm"))]
         {
-            use crate::env::internal::xdr;
-            use stellar_strkey::{ed25519, Contract, Strkey};
-            let sc_val = ScVal::try_from(self).map_err(|_| core::fmt::Error)?;
-            if let ScVal::Object(Some(xdr::ScObject::Address(addr))) = sc_val {
-                match addr {
-                    xdr::ScAddress::Account(account_id) => {
-                        let xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
-                            ed25519,
-                        ))) = account_id;
-                        let strkey = Strkey::PublicKeyEd25519(ed25519::PublicKey(ed25519));
-                        write!(f, "AccountId({})", strkey.to_string())?;
-                    }
-                    xdr::ScAddress::Contract(contract_id) => {
-                        let strkey = Strkey::Contract(Contract(contract_id.0));
-                        write!(f, "Contract({})", strkey.to_string())?;
-                    }
+            let strkey = self.to_strkey().map_err(|_| core::fmt::Error)?;
+            match strkey {
+                stellar_strkey::Strkey::PublicKeyEd25519(_) => {
+                    write!(f, "AccountId({})", strkey.to_string())?;
                 }
-            } else {
-                return Err(core::fmt::Error);
+                stellar_strkey::Strkey::Contract(_) => {
+                    write!(f, "Contract({})", strkey.to_string())?;
+                }
+                _ => return Err(core::fmt::Error),
             }
         }
         Ok(())
     }
 }
 
+/// Renders the Address as its canonical strkey: `G...` for a Stellar
+/// account, `C...` for a contract. Use `to_string()` (via the blanket
+/// `ToString` impl) to get an owned `String`.
+#[cfg(not(target_family = "wasm"))]
+impl core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let strkey = self.to_strkey().map_err(|_| core::fmt::Error)?;
+        write!(f, "{}", strkey.to_string())
+    }
+}
+
 impl Eq for Address {}
 
 impl PartialEq for Address {
@@ -170,7 +171,15 @@ impl Address {
     /// When called in the tests, the `require_auth` calls are just recorded and
     /// no signatures are required. In order to make sure that the contract
     /// has indeed called `require_auth` for this Address with expected arguments
-    /// use `env.verify_top_authorization`.
+    /// use `env.verify_top_authorization`, or inspect all the recorded
+    /// top-level authorizations via
+    /// [`testutils::Env::auths`](crate::testutils::Env::auths) (sub-invocations
+    /// are not recorded).
+    ///
+    /// When this Address identifies a custom account contract (see
+    /// [`crate::auth::CustomAccountInterface`]), this may be called again
+    /// re-entrantly from within that contract's `__check_auth`, e.g. to
+    /// delegate part of the authorization decision to another Address.
     ///
     /// ### Panics
     ///
@@ -222,6 +231,45 @@ impl Address {
     pub fn to_object(&self) -> Object {
         self.obj
     }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn to_strkey(&self) -> Result<stellar_strkey::Strkey, ConversionError> {
+        use crate::env::internal::xdr;
+        use stellar_strkey::{ed25519, Contract, Strkey};
+        let sc_val = ScVal::try_from(self).map_err(|_| ConversionError {})?;
+        if let ScVal::Object(Some(xdr::ScObject::Address(addr))) = sc_val {
+            Ok(match addr {
+                xdr::ScAddress::Account(account_id) => {
+                    let xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+                        ed25519,
+                    ))) = account_id;
+                    Strkey::PublicKeyEd25519(ed25519::PublicKey(ed25519))
+                }
+                xdr::ScAddress::Contract(contract_id) => Strkey::Contract(Contract(contract_id.0)),
+            })
+        } else {
+            Err(ConversionError {})
+        }
+    }
+
+    /// Parses a strkey (`G...` for a Stellar account, `C...` for a
+    /// contract) into an Address.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn from_string(env: &Env, s: &str) -> Result<Address, ConversionError> {
+        use crate::env::internal::xdr::{self, Hash, ScAddress, ScObject};
+        use stellar_strkey::{ed25519, Contract, Strkey};
+        let sc_addr = match Strkey::from_string(s).map_err(|_| ConversionError {})? {
+            Strkey::PublicKeyEd25519(ed25519::PublicKey(key)) => {
+                ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(
+                    xdr::Uint256(key),
+                )))
+            }
+            Strkey::Contract(Contract(id)) => ScAddress::Contract(Hash(id)),
+            _ => return Err(ConversionError {}),
+        };
+        let sc_val = ScVal::Object(Some(ScObject::Address(sc_addr)));
+        Self::try_from_val(env, &sc_val)
+    }
 }
 
 #[cfg(any(test, feature = "testutils"))]
@@ -0,0 +1,225 @@
+//! Support for writing custom account contracts.
+//!
+//! An [`Address`] may identify either a Stellar account or a contract. When
+//! it identifies a contract, that contract is free to act as a custom
+//! "account contract" that implements its own authentication and
+//! authorization logic (for example threshold or multisig schemes, time
+//! bounds, or delegation to other addresses).
+//!
+//! A contract opts into this by implementing [`CustomAccountInterface`] and
+//! exporting `__check_auth` itself, decoding the host-supplied arguments and
+//! dispatching to the trait via [`invoke_check_auth`]:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub extern "C" fn __check_auth(
+//!     env: Env,
+//!     signature_payload: RawVal,
+//!     signatures: RawVal,
+//!     auth_contexts: RawVal,
+//! ) -> RawVal {
+//!     soroban_sdk::auth::invoke_check_auth::<MyAccountContract>(
+//!         env,
+//!         signature_payload,
+//!         signatures,
+//!         auth_contexts,
+//!     )
+//! }
+//! ```
+//!
+//! `#[contractimpl]` does not yet special-case `CustomAccountInterface` to
+//! generate this export automatically; until it does, the snippet above is
+//! required.
+//!
+//! During authorization the host computes `signature_payload` as the
+//! SHA-256 hash of an `HashIdPreimageSorobanAuthorization` (the network id,
+//! the nonce, and the tree of invocations being authorized) and invokes
+//! `__check_auth` with that hash, the `signatures` supplied by the caller,
+//! and `auth_contexts` describing every [`Address::require_auth`] /
+//! [`Address::require_auth_for_args`] call being authorized, including
+//! those performed by sub-invocations. The contract is responsible for
+//! verifying the signatures against the payload and deciding whether the
+//! requested invocations should be allowed, returning `Ok(())` to approve
+//! them or `Err` to reject.
+//!
+//! [`Address::require_auth`] may be called again from within `__check_auth`
+//! itself - the host supports this re-entrantly so that a custom account can
+//! delegate part of its authorization decision to another address (for
+//! example a multisig contract that requires each of its signers to
+//! separately authorize the signature check).
+//!
+//! [`Address::require_auth`]: crate::Address::require_auth
+//! [`Address::require_auth_for_args`]: crate::Address::require_auth_for_args
+
+use crate::{
+    env::internal::xdr, unwrap::UnwrapInfallible, Address, BytesN, ConversionError, Env, RawVal,
+    Symbol, TryFromVal, TryIntoVal, Vec,
+};
+
+/// A single invocation being authorized, as passed to
+/// [`CustomAccountInterface::__check_auth`].
+///
+/// Mirrors the host's notion of an authorized invocation, which is either a
+/// contract function call ([`ContractContext`], the common case) or a
+/// `create_contract` host function. The latter isn't a contract invocation,
+/// so this SDK doesn't yet expose a structured accessor for it; its payload
+/// is preserved as an opaque [`RawVal`] rather than guessed at and
+/// potentially misdecoded.
+#[derive(Clone, Debug)]
+pub enum Context {
+    Contract(ContractContext),
+    CreateContractHostFn(RawVal),
+}
+
+/// Describes one `require_auth`/`require_auth_for_args` call found in the
+/// invocation tree rooted at the transaction, i.e. the contract that
+/// performed the call, the function being invoked and the arguments it was
+/// invoked with.
+#[derive(Clone, Debug)]
+pub struct ContractContext {
+    /// Address of the contract that is being authorized to call `fn_name`.
+    pub contract: Address,
+    /// Name of the function being authorized.
+    pub fn_name: Symbol,
+    /// Arguments supplied for the authorized `require_auth_for_args` call.
+    pub args: Vec<RawVal>,
+}
+
+impl TryFromVal<Env, RawVal> for Context {
+    type Error = ConversionError;
+
+    fn try_from_val(env: &Env, val: &RawVal) -> Result<Self, Self::Error> {
+        let fields: Vec<RawVal> = Vec::try_from_val(env, val).map_err(|_| ConversionError {})?;
+        if fields.len() != 3 {
+            // Not the (contract, fn_name, args) shape of a contract
+            // invocation context; treat it as an opaque create_contract
+            // context rather than erroring, since a real `auth_contexts`
+            // tree may legitimately contain both kinds.
+            return Ok(Context::CreateContractHostFn(*val));
+        }
+        Ok(Context::Contract(ContractContext {
+            contract: Address::try_from_val(env, &fields.get_unchecked(0))?,
+            fn_name: Symbol::try_from_val(env, &fields.get_unchecked(1))?,
+            args: Vec::try_from_val(env, &fields.get_unchecked(2)).map_err(|_| ConversionError {})?,
+        }))
+    }
+}
+
+/// Error returned by [`CustomAccountInterface::__check_auth`] to reject an
+/// authorization attempt.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthError;
+
+impl From<AuthError> for crate::Error {
+    fn from(_: AuthError) -> Self {
+        crate::Error::from_contract_error(0)
+    }
+}
+
+/// Trait for contracts that implement custom account authentication and
+/// authorization logic.
+///
+/// See the [module-level documentation](self) for how this is wired up by
+/// the host.
+pub trait CustomAccountInterface {
+    /// Verifies `signatures` against `signature_payload` and decides
+    /// whether they authorize every invocation listed in `auth_contexts`.
+    ///
+    /// Returning `Ok(())` approves the authorization; returning `Err`
+    /// rejects it.
+    fn __check_auth(
+        env: Env,
+        signature_payload: BytesN<32>,
+        signatures: Vec<RawVal>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), AuthError>;
+}
+
+/// Decodes the host-supplied `__check_auth` arguments and dispatches to
+/// `T::__check_auth`. See the [module-level documentation](self) for the
+/// `#[no_mangle] extern "C" fn __check_auth` export that should call this.
+///
+/// ### Panics
+///
+/// If the arguments can't be decoded. If `T::__check_auth` rejects the
+/// authorization, its [`AuthError`] is converted to a [`crate::Error`] and
+/// returned to the host rather than panicking, so the host can report the
+/// specific rejection reason instead of a generic trap.
+pub fn invoke_check_auth<T: CustomAccountInterface>(
+    env: Env,
+    signature_payload: RawVal,
+    signatures: RawVal,
+    auth_contexts: RawVal,
+) -> RawVal {
+    let signature_payload = BytesN::<32>::try_from_val(&env, &signature_payload)
+        .unwrap_or_else(|_| panic!("invalid signature_payload"));
+    let signatures = Vec::<RawVal>::try_from_val(&env, &signatures)
+        .unwrap_or_else(|_| panic!("invalid signatures"));
+    let auth_contexts = Vec::<Context>::try_from_val(&env, &auth_contexts)
+        .unwrap_or_else(|_| panic!("invalid auth_contexts"));
+    match T::__check_auth(env.clone(), signature_payload, signatures, auth_contexts) {
+        Ok(()) => ().try_into_val(&env).unwrap_infallible(),
+        Err(e) => crate::Error::from(e).try_into_val(&env).unwrap_infallible(),
+    }
+}
+
+/// Computes the payload that must be signed in order to authorize
+/// `function(args)` on `contract`, for the given `nonce`, with a signature
+/// that is valid up to and including `signature_expiration_ledger`.
+///
+/// This is the SHA-256 hash of a `HashIdPreimageSorobanAuthorization` built
+/// from the current ledger's network id, `nonce`, `signature_expiration_ledger`
+/// and the invocation tree rooted at `contract.function(args)` - exactly
+/// what the host computes when verifying a
+/// `require_auth`/`require_auth_for_args` call. `signature_expiration_ledger`
+/// must match the `signature_expiration_ledger` of the auth entry the
+/// resulting signature is submitted with, or verification will fail.
+/// Off-chain signers (and the `testutils::ed25519` signing helpers) can use
+/// this to build the signature payload without reimplementing the XDR
+/// encoding.
+///
+/// This builds and encodes XDR directly, which (like [`Address::from_string`])
+/// is only available host-side, not from a wasm contract - custom account
+/// contracts don't call this themselves, they just verify `signature_payload`
+/// as given to `__check_auth`.
+///
+/// [`Address::from_string`]: crate::Address::from_string
+#[cfg(not(target_family = "wasm"))]
+pub fn authorization_hash(
+    env: &Env,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    contract: &Address,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> Result<BytesN<32>, ConversionError> {
+    use crate::{env::internal::xdr::WriteXdr, Bytes};
+
+    let contract_address = match xdr::ScVal::try_from(contract).map_err(|_| ConversionError {})? {
+        xdr::ScVal::Object(Some(xdr::ScObject::Address(addr))) => addr,
+        _ => return Err(ConversionError {}),
+    };
+    let function_name = function.try_into().map_err(|_| ConversionError {})?;
+    let args = args.try_into().map_err(|_| ConversionError {})?;
+    let network_id = env.ledger().network_id();
+    let invocation = xdr::SorobanAuthorizedInvocation {
+        function: xdr::SorobanAuthorizedFunction::ContractFn(
+            xdr::SorobanAuthorizedContractFunction {
+                contract_address,
+                function_name,
+                args,
+            },
+        ),
+        sub_invocations: xdr::VecM::default(),
+    };
+    let preimage = xdr::HashIdPreimage::SorobanAuthorization(
+        xdr::HashIdPreimageSorobanAuthorization {
+            network_id: xdr::Hash(network_id.to_array()),
+            nonce,
+            signature_expiration_ledger,
+            invocation,
+        },
+    );
+    let payload = preimage.to_xdr().map_err(|_| ConversionError {})?;
+    Ok(env.crypto().sha256(&Bytes::from_slice(env, &payload)))
+}
@@ -0,0 +1,31 @@
+#![cfg(test)]
+#![cfg(feature = "testutils")]
+
+extern crate std;
+use std::string::ToString;
+
+use crate::testutils::{Address as _, Env as _};
+
+#[test]
+fn test_prng_seed_makes_address_random_reproducible() {
+    let env = crate::Env::default();
+    env.set_prng_seed([1; 32]);
+    let a = crate::Address::random(&env);
+    let b = crate::Address::random(&env);
+
+    let env = crate::Env::default();
+    env.set_prng_seed([1; 32]);
+    let c = crate::Address::random(&env);
+    let d = crate::Address::random(&env);
+
+    assert_eq!(a, c);
+    assert_eq!(b, d);
+}
+
+#[test]
+fn test_address_from_string_round_trips_to_string() {
+    let env = crate::Env::default();
+    let a = crate::Address::random(&env);
+    let s = a.to_string();
+    assert_eq!(crate::Address::from_string(&env, &s).unwrap(), a);
+}
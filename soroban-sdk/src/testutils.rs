@@ -0,0 +1,193 @@
+//! Utilities intended for use when testing contracts.
+#![cfg(any(test, feature = "testutils"))]
+#![cfg_attr(feature = "docs", doc(cfg(feature = "testutils")))]
+
+extern crate std;
+use std::{cell::RefCell, vec::Vec as StdVec};
+
+use rand::{CryptoRng, Error as RandError, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{BytesN, Env, RawVal, Symbol, Vec};
+
+pub mod ed25519;
+
+std::thread_local! {
+    // The PRNG backing `random()`. `None` until `set_prng_seed` is called,
+    // at which point it switches from the nondeterministic `thread_rng` to
+    // a deterministic, seeded stream so that failing tests can be
+    // reproduced exactly.
+    static PRNG: RefCell<Option<ChaCha20Rng>> = RefCell::new(None);
+}
+
+/// A concrete RNG that is either the deterministic seeded stream set via
+/// `set_prng_seed`, or the nondeterministic default. Unlike `&mut dyn
+/// RngCore`, this is a concrete type that can soundly implement the marker
+/// trait `CryptoRng`, so it can be handed to APIs (like
+/// `ed25519_dalek::Keypair::generate`) that require a `CryptoRng`.
+enum TestRng<'a> {
+    Seeded(&'a mut ChaCha20Rng),
+    NonDeterministic(rand::rngs::ThreadRng),
+}
+
+impl RngCore for TestRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            TestRng::Seeded(rng) => rng.next_u32(),
+            TestRng::NonDeterministic(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            TestRng::Seeded(rng) => rng.next_u64(),
+            TestRng::NonDeterministic(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            TestRng::Seeded(rng) => rng.fill_bytes(dest),
+            TestRng::NonDeterministic(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), RandError> {
+        match self {
+            TestRng::Seeded(rng) => rng.try_fill_bytes(dest),
+            TestRng::NonDeterministic(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+// `ChaCha20Rng` and `ThreadRng` are both cryptographically secure, so
+// `TestRng` is too.
+impl CryptoRng for TestRng<'_> {}
+
+pub(crate) fn random() -> [u8; 32] {
+    with_rng(|rng| {
+        let mut result = [0u8; 32];
+        rng.fill_bytes(&mut result);
+        result
+    })
+}
+
+pub(crate) fn with_rng<R>(f: impl FnOnce(&mut TestRng) -> R) -> R {
+    PRNG.with(|prng| match prng.borrow_mut().as_mut() {
+        Some(rng) => f(&mut TestRng::Seeded(rng)),
+        None => f(&mut TestRng::NonDeterministic(rand::thread_rng())),
+    })
+}
+
+pub(crate) fn seed_prng(seed: [u8; 32]) {
+    PRNG.with(|prng| *prng.borrow_mut() = Some(ChaCha20Rng::from_seed(seed)));
+}
+
+/// Test utilities for [`crate::Address`].
+pub trait Address {
+    /// Generate a new Address backed by a random contract id.
+    ///
+    /// Implies that the Address does not exist on the ledger until it is
+    /// used for the first time.
+    fn random(env: &Env) -> Self;
+
+    /// Constructs an Address corresponding to the contract with the given id.
+    fn from_contract_id(env: &Env, contract_id: &BytesN<32>) -> Self;
+}
+
+/// Describes a single `require_auth`/`require_auth_for_args` call that was
+/// authorized: the contract whose invocation was authorized, the function
+/// and arguments it was invoked with.
+///
+/// This SDK's recording-mode auth tracker (the same one backing
+/// `Env::verify_top_authorization`) only tracks top-level calls, not a
+/// transitive call tree, so `sub_invocations` is always empty. It is kept on
+/// the struct for forward compatibility with hosts that do track
+/// sub-invocations.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizedInvocation {
+    /// Address of the contract whose function was invoked.
+    pub contract: crate::Address,
+    /// Name of the invoked function.
+    pub fn_name: Symbol,
+    /// Arguments the function was invoked with.
+    pub args: Vec<RawVal>,
+    /// Always empty: this SDK version's auth tracker doesn't record
+    /// sub-invocations. See the struct-level docs.
+    pub sub_invocations: StdVec<AuthorizedInvocation>,
+}
+
+/// Test utilities for [`crate::Env`].
+pub trait Env {
+    /// Returns the authorizations recorded by the auth tracker (the same one
+    /// backing `Env::verify_top_authorization`) since the last contract
+    /// invocation, one entry per `Address` that had
+    /// `require_auth`/`require_auth_for_args` called on it.
+    ///
+    /// This can be used to assert that a contract required exactly the
+    /// authorizations it was expected to, rather than checking one
+    /// authorization at a time via `verify_top_authorization`.
+    fn auths(&self) -> StdVec<(crate::Address, AuthorizedInvocation)>;
+
+    /// Pre-seeds the recorded authorizations returned by `auths` with the
+    /// provided entries, overriding whatever has been recorded so far.
+    ///
+    /// Useful for asserting the exact set of authorizations a contract
+    /// invocation requires before the invocation that would produce them
+    /// has been made.
+    fn set_auths(&self, auths: &[(crate::Address, AuthorizedInvocation)]);
+
+    /// Seeds the test PRNG backing `Address::random`, contract id
+    /// generation and [`ed25519::generate`], making them deterministic: the
+    /// same seed always produces the same sequence of addresses and keys,
+    /// so a failing test can be reproduced exactly by fixing the seed it
+    /// used.
+    ///
+    /// The seed is shared by every `Env` on the current thread (there is no
+    /// per-`Env` PRNG state to seed independently), so tests that rely on
+    /// it should not run concurrently with other seeded tests on the same
+    /// thread. Without calling this, those functions draw from a
+    /// nondeterministic source as before.
+    fn set_prng_seed(&self, seed: [u8; 32]);
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Env for crate::Env {
+    fn auths(&self) -> StdVec<(crate::Address, AuthorizedInvocation)> {
+        self.get_recorded_top_authorizations()
+            .unwrap_or_else(|_| panic!("failed to read recorded authorizations"))
+            .into_iter()
+            .map(|(address, contract, fn_name, args)| {
+                (
+                    address,
+                    AuthorizedInvocation {
+                        contract,
+                        fn_name,
+                        args,
+                        sub_invocations: StdVec::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn set_auths(&self, auths: &[(crate::Address, AuthorizedInvocation)]) {
+        let entries = auths
+            .iter()
+            .map(|(address, invocation)| {
+                (
+                    address.clone(),
+                    invocation.contract.clone(),
+                    invocation.fn_name.clone(),
+                    invocation.args.clone(),
+                )
+            })
+            .collect();
+        self.set_recorded_top_authorizations(entries)
+            .unwrap_or_else(|_| panic!("failed to set recorded authorizations"));
+    }
+
+    fn set_prng_seed(&self, seed: [u8; 32]) {
+        seed_prng(seed)
+    }
+}
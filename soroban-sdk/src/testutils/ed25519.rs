@@ -0,0 +1,62 @@
+//! Test utilities for generating ed25519 keys and signing the
+//! `Address`-based authorization payload (see [`crate::auth`]).
+//!
+//! [`generate`] draws from the same PRNG as `Address::random`, and [`sign`]
+//! uses no randomness at all (ed25519 signatures are deterministic), so
+//! seeding the PRNG via
+//! [`testutils::Env::set_prng_seed`](super::Env::set_prng_seed) makes an
+//! entire test, including the keys and signatures it produces,
+//! deterministic.
+
+extern crate std;
+
+use ed25519_dalek::{Keypair, Signer};
+
+use super::with_rng;
+use crate::{auth::authorization_hash, Address, BytesN, Env, RawVal, Symbol, Vec};
+
+/// Generates a new ed25519 keypair together with the `Address` identifying
+/// it.
+pub fn generate(env: &Env) -> (Address, Keypair) {
+    use crate::{
+        env::internal::xdr::{self, ScAddress, ScObject, ScVal},
+        TryFromVal,
+    };
+    let keypair = with_rng(|rng| Keypair::generate(rng));
+    let sc_addr = ScVal::Object(Some(ScObject::Address(ScAddress::Account(
+        xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+            keypair.public.to_bytes(),
+        ))),
+    ))));
+    let address = Address::try_from_val(env, &sc_addr).unwrap();
+    (address, keypair)
+}
+
+/// Signs the authorization payload for `contract.function(args)` under
+/// `nonce`, valid up to and including `signature_expiration_ledger`,
+/// producing the 64-byte signature a contract's `__check_auth` (or the
+/// host's built-in account verification) expects.
+///
+/// `signature_expiration_ledger` must match the `signature_expiration_ledger`
+/// of the auth entry this signature is submitted with.
+pub fn sign(
+    env: &Env,
+    keypair: &Keypair,
+    nonce: i64,
+    signature_expiration_ledger: u32,
+    contract: &Address,
+    function: Symbol,
+    args: Vec<RawVal>,
+) -> BytesN<64> {
+    let payload = authorization_hash(
+        env,
+        nonce,
+        signature_expiration_ledger,
+        contract,
+        function,
+        args,
+    )
+    .unwrap_or_else(|_| panic!("contract, function or args could not be encoded to XDR"));
+    let signature = keypair.sign(&payload.to_array());
+    BytesN::from_array(env, &signature.to_bytes())
+}